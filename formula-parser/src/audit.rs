@@ -0,0 +1,220 @@
+// formula-parser/src/audit.rs
+//
+// A lightweight lint pass over an already-parsed `Formula`, modeled on
+// Homebrew's own auditors: a handful of offline metadata checks, plus
+// optional checks against the GitHub/GitLab API when `online` is set.
+
+use crate::Formula;
+use std::process::Command;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Warn,
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditWarning {
+    pub severity: Severity,
+    pub message: String,
+}
+
+fn warn(message: impl Into<String>) -> AuditWarning {
+    AuditWarning {
+        severity: Severity::Warn,
+        message: message.into(),
+    }
+}
+
+fn error(message: impl Into<String>) -> AuditWarning {
+    AuditWarning {
+        severity: Severity::Error,
+        message: message.into(),
+    }
+}
+
+/// Runs the lint pass over a parsed formula. Set `online` to also query
+/// the formula's GitHub/GitLab host for prerelease/archived-repo warnings.
+pub fn audit(formula: &Formula, online: bool) -> Vec<AuditWarning> {
+    let mut warnings = Vec::new();
+
+    audit_desc(formula, &mut warnings);
+    audit_homepage(formula, &mut warnings);
+    audit_url(formula, &mut warnings);
+    audit_sha256(formula, &mut warnings);
+
+    if online {
+        audit_online(formula, &mut warnings);
+    }
+
+    warnings
+}
+
+fn audit_desc(formula: &Formula, warnings: &mut Vec<AuditWarning>) {
+    match &formula.description {
+        None => warnings.push(error("desc is missing")),
+        Some(desc) => {
+            if desc.to_lowercase().starts_with(&formula.name.to_lowercase()) {
+                warnings.push(warn("desc should not start with the formula name"));
+            } else if desc.starts_with("A ") || desc.starts_with("An ") {
+                warnings.push(warn("desc should not start with \"A\" or \"An\""));
+            }
+        }
+    }
+}
+
+fn audit_homepage(formula: &Formula, warnings: &mut Vec<AuditWarning>) {
+    match &formula.homepage {
+        None => warnings.push(error("homepage is missing")),
+        Some(homepage) if !homepage.starts_with("https://") => {
+            warnings.push(warn("homepage should use https"));
+        }
+        Some(_) => {}
+    }
+}
+
+fn audit_url(formula: &Formula, warnings: &mut Vec<AuditWarning>) {
+    if formula.url.is_none() {
+        warnings.push(error("url is missing"));
+    }
+}
+
+fn audit_sha256(formula: &Formula, warnings: &mut Vec<AuditWarning>) {
+    match &formula.sha256 {
+        None => warnings.push(error("sha256 is missing")),
+        Some(sha256) => {
+            let is_hex64 = sha256.len() == 64 && sha256.chars().all(|c| c.is_ascii_hexdigit());
+            if !is_hex64 {
+                warnings.push(error("sha256 is not a well-formed 64-character hex digest"));
+            }
+        }
+    }
+}
+
+/// Extracts the `user`/`repo` pair out of a GitHub or GitLab URL, mirroring
+/// `https?://(github|gitlab)\.com/([^/]+)/([^/]+)` while guarding against a
+/// missing repo segment.
+fn github_like_repo(url: &str) -> Option<(&'static str, String, String)> {
+    for (host, prefix) in [("github", "github.com/"), ("gitlab", "gitlab.com/")] {
+        let rest = match url.find(prefix) {
+            Some(idx) => &url[idx + prefix.len()..],
+            None => continue,
+        };
+        let mut parts = rest.splitn(3, '/');
+        let user = parts.next().filter(|s| !s.is_empty());
+        let repo = parts.next().filter(|s| !s.is_empty());
+        if let (Some(user), Some(repo)) = (user, repo) {
+            let repo = repo.trim_end_matches(".git");
+            return Some((host, user.to_string(), repo.to_string()));
+        }
+    }
+    None
+}
+
+fn audit_online(formula: &Formula, warnings: &mut Vec<AuditWarning>) {
+    let url = match &formula.url {
+        Some(url) => url,
+        None => return,
+    };
+    let (host, user, repo) = match github_like_repo(url) {
+        Some(parsed) => parsed,
+        None => return,
+    };
+
+    let repo_api_url = match host {
+        "github" => format!("https://api.github.com/repos/{}/{}", user, repo),
+        _ => format!("https://gitlab.com/api/v4/projects/{}%2F{}", user, repo),
+    };
+    if let Ok(body) = fetch(&repo_api_url) {
+        if body.contains("\"archived\":true") || body.contains("\"archived\": true") {
+            warnings.push(warn(format!("{}/{} is archived", user, repo)));
+        }
+    }
+
+    let latest_release_url = match host {
+        "github" => format!("https://api.github.com/repos/{}/{}/releases/latest", user, repo),
+        _ => format!("https://gitlab.com/api/v4/projects/{}%2F{}/releases", user, repo),
+    };
+    if let Ok(body) = fetch(&latest_release_url) {
+        if body.contains("\"prerelease\":true") || body.contains("\"prerelease\": true") {
+            warnings.push(warn("latest release looks like a prerelease"));
+        }
+    }
+}
+
+fn fetch(url: &str) -> Result<String, std::io::Error> {
+    let output = Command::new("curl").args(["-fsSL", url]).output()?;
+    if !output.status.success() {
+        return Err(std::io::Error::new(std::io::ErrorKind::Other, "request failed"));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn complete_formula() -> Formula {
+        Formula {
+            name: "example".to_string(),
+            description: Some("Tool for doing things".to_string()),
+            homepage: Some("https://example.com".to_string()),
+            url: Some("https://example.com/example-1.0.tar.gz".to_string()),
+            sha256: Some("a".repeat(64)),
+            dependencies: Vec::new(),
+            bottles: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn flags_missing_desc() {
+        let mut formula = complete_formula();
+        formula.description = None;
+
+        let warnings = audit(&formula, false);
+
+        assert!(warnings
+            .iter()
+            .any(|w| w.severity == Severity::Error && w.message == "desc is missing"));
+    }
+
+    #[test]
+    fn flags_desc_starting_with_formula_name() {
+        let mut formula = complete_formula();
+        formula.description = Some("Example tool for doing things".to_string());
+
+        let warnings = audit(&formula, false);
+
+        assert!(warnings
+            .iter()
+            .any(|w| w.message == "desc should not start with the formula name"));
+    }
+
+    #[test]
+    fn flags_non_https_homepage() {
+        let mut formula = complete_formula();
+        formula.homepage = Some("http://example.com".to_string());
+
+        let warnings = audit(&formula, false);
+
+        assert!(warnings.iter().any(|w| w.message == "homepage should use https"));
+    }
+
+    #[test]
+    fn flags_malformed_sha256() {
+        let mut formula = complete_formula();
+        formula.sha256 = Some("not-a-real-digest".to_string());
+
+        let warnings = audit(&formula, false);
+
+        assert!(warnings
+            .iter()
+            .any(|w| w.message == "sha256 is not a well-formed 64-character hex digest"));
+    }
+
+    #[test]
+    fn complete_formula_has_no_offline_warnings() {
+        let formula = complete_formula();
+        assert!(audit(&formula, false).is_empty());
+    }
+}
@@ -0,0 +1,176 @@
+// formula-parser/src/cask.rs
+//
+// Casks use a DSL that sits alongside `Formula` but is driven through a
+// single top-level `cask(token, &block)` call rather than a subclass, so
+// it needs its own recorder instead of reusing the `Formula` bridge.
+
+use magnus::{prelude::*, Ruby};
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Default)]
+pub struct Cask {
+    pub token: String,
+    pub name: Vec<String>,
+    pub version: Option<String>,
+    pub sha256: Option<String>,
+    pub url: Option<String>,
+    pub homepage: Option<String>,
+    pub artifacts: Vec<Artifact>,
+}
+
+/// An artifact stanza (`app`, `pkg`, `binary`, `zap`) from a cask's body.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Artifact {
+    App(String),
+    Pkg(String),
+    Binary(String),
+    Zap,
+}
+
+pub fn parse_cask(path: &Path) -> Result<Cask, magnus::Error> {
+    let file_stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+
+    let ruby = match Ruby::get() {
+        Ok(ruby) => ruby,
+        Err(e) => return Err(magnus::Error::new(magnus::exception::runtime_error(), e.to_string())),
+    };
+
+    let file_content = fs::read_to_string(path)
+        .map_err(|e| magnus::Error::new(magnus::exception::runtime_error(), e.to_string()))?;
+
+    // Unlike the formula path we can't pre-extract lines here, since the
+    // cask body is just arguments to method calls inside a block. Evaluate
+    // the whole file body and read values back from the recorder it built.
+    let inspector_code = format!(
+        r#"
+        class CaskRecorder
+          def initialize(token)
+            @token = token
+            @values = {{}}
+            @artifacts = []
+          end
+
+          attr_reader :token, :artifacts
+
+          def version(value = nil)
+            value ? (@values[:version] = value) : @values[:version]
+          end
+
+          def sha256(value = nil)
+            value ? (@values[:sha256] = value) : @values[:sha256]
+          end
+
+          def url(value = nil)
+            value ? (@values[:url] = value) : @values[:url]
+          end
+
+          def homepage(value = nil)
+            value ? (@values[:homepage] = value) : @values[:homepage]
+          end
+
+          def name(value = nil)
+            if value
+              @values[:names] ||= []
+              @values[:names] << value
+            else
+              @values[:names] || []
+            end
+          end
+
+          def app(value)
+            @artifacts << ["app", value]
+          end
+
+          def pkg(value)
+            @artifacts << ["pkg", value]
+          end
+
+          def binary(value)
+            @artifacts << ["binary", value]
+          end
+
+          def zap(*args)
+            @artifacts << ["zap", nil]
+          end
+
+          # The whole cask body is instance_exec'd at once (unlike the
+          # formula path's line filter), so any stanza we don't model yet
+          # — depends_on, caveats, livecheck, auto_updates, conflicts_with,
+          # preflight/postflight, and anything added to the DSL later —
+          # needs to be a no-op rather than raising NoMethodError.
+          def method_missing(name, *args, &block)
+            nil
+          end
+
+          def respond_to_missing?(name, include_private = false)
+            true
+          end
+        end
+
+        def cask(token, &block)
+          recorder = CaskRecorder.new(token)
+          recorder.instance_exec(&block)
+          $cask_recorder = recorder
+        end
+
+        {}
+    "#,
+        file_content
+    );
+
+    ruby.eval::<magnus::Value>(&inspector_code)?;
+
+    let recorder: magnus::Value = ruby.eval("$cask_recorder")?;
+
+    let token: String = recorder.funcall("token", ()).unwrap_or_else(|_| file_stem.to_string());
+    let name: Vec<String> = recorder.funcall("name", ()).unwrap_or_default();
+    let version: Option<String> = recorder.funcall("version", ()).ok();
+    let sha256: Option<String> = recorder.funcall("sha256", ()).ok();
+    let url: Option<String> = recorder.funcall("url", ()).ok();
+    let homepage: Option<String> = recorder.funcall("homepage", ()).ok();
+
+    let raw_artifacts: Vec<(String, Option<String>)> =
+        recorder.funcall("artifacts", ()).unwrap_or_default();
+    let artifacts = raw_artifacts
+        .into_iter()
+        .filter_map(|(kind, value)| match kind.as_str() {
+            "app" => value.map(Artifact::App),
+            "pkg" => value.map(Artifact::Pkg),
+            "binary" => value.map(Artifact::Binary),
+            "zap" => Some(Artifact::Zap),
+            _ => None,
+        })
+        .collect();
+
+    Ok(Cask {
+        token,
+        name,
+        version,
+        sha256,
+        url,
+        homepage,
+        artifacts,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_parses_a_real_world_cask_with_unmodeled_stanzas() {
+        let _cleanup = unsafe { magnus::embed::init() };
+
+        let path_to_cask = Path::new("tests/fixtures/real-world-app.rb");
+        let cask = parse_cask(path_to_cask).expect("Failed to parse cask");
+
+        assert_eq!(cask.token, "real-world-app");
+        assert_eq!(cask.version.as_deref(), Some("2.1.0"));
+        assert_eq!(cask.name, vec!["Real World App".to_string()]);
+        assert_eq!(
+            cask.artifacts,
+            vec![Artifact::App("Real World App.app".to_string()), Artifact::Zap]
+        );
+    }
+}
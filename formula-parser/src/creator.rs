@@ -0,0 +1,175 @@
+// formula-parser/src/creator.rs
+//
+// The inverse of `parse_formula`: given a name, version, and source URL,
+// render a formula skeleton the way `brew create` would, with an
+// `install` body tailored to the project's build system.
+
+use crate::class_name_from;
+
+/// Build system a generated formula's `install` method should target.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Autotools,
+    Cmake,
+    Meson,
+    Go,
+    Rust,
+    Perl,
+    Python,
+    Ruby,
+    Node,
+    Cabal,
+    Crystal,
+}
+
+fn install_body(mode: Mode) -> &'static str {
+    match mode {
+        Mode::Autotools => {
+            "    system \"./configure\", \"--disable-silent-rules\", \"--prefix=#{prefix}\"\n    system \"make\", \"install\"\n"
+        }
+        Mode::Cmake => {
+            "    system \"cmake\", \"-S\", \".\", \"-B\", \"build\", *std_cmake_args\n    system \"cmake\", \"--build\", \"build\"\n    system \"cmake\", \"--install\", \"build\"\n"
+        }
+        Mode::Meson => {
+            "    system \"meson\", \"setup\", \"build\", *std_meson_args\n    system \"meson\", \"compile\", \"-C\", \"build\"\n    system \"meson\", \"install\", \"-C\", \"build\"\n"
+        }
+        Mode::Go => "    system \"go\", \"build\", *std_go_args(output: bin/name)\n",
+        Mode::Rust => "    system \"cargo\", \"install\", *std_cargo_args\n",
+        Mode::Perl => {
+            "    system \"perl\", \"Makefile.PL\", \"INSTALL_BASE=#{prefix}\"\n    system \"make\", \"install\"\n"
+        }
+        Mode::Python => "    system Formula[\"python3\"].opt_bin/\"python3\", \"-m\", \"pip\", \"install\", *std_pip_args, \".\"\n",
+        Mode::Ruby => "    system \"gem\", \"build\", \"#{name}.gemspec\"\n    system \"gem\", \"install\", *std_gem_args\n",
+        Mode::Node => "    system \"npm\", \"install\", *std_npm_args\n",
+        Mode::Cabal => "    system \"cabal\", \"v2-install\", *std_cabal_v2_args\n",
+        Mode::Crystal => "    system \"crystal\", \"build\", \"src/#{name}.cr\", *std_crystal_args\n",
+    }
+}
+
+/// Builder for a generated `.rb` formula, modeled on Homebrew's own
+/// formula constructor: `FormulaCreator::new(...)` then chained setters,
+/// finishing with `render()`.
+pub struct FormulaCreator {
+    name: String,
+    version: String,
+    url: String,
+    tap: Option<String>,
+    license: Option<String>,
+    mode: Option<Mode>,
+}
+
+impl FormulaCreator {
+    pub fn new(name: impl Into<String>, version: impl Into<String>, url: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            version: version.into(),
+            url: url.into(),
+            tap: None,
+            license: None,
+            mode: None,
+        }
+    }
+
+    pub fn tap(mut self, tap: impl Into<String>) -> Self {
+        self.tap = Some(tap.into());
+        self
+    }
+
+    pub fn license(mut self, license: impl Into<String>) -> Self {
+        self.license = Some(license.into());
+        self
+    }
+
+    pub fn mode(mut self, mode: Mode) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    /// Renders the formula source. The `desc`, `homepage`, and `sha256`
+    /// lines are left blank, matching what `brew create` leaves for the
+    /// author to fill in by hand.
+    pub fn render(&self) -> String {
+        let class_name = class_name_from(&self.name);
+
+        let mut out = String::new();
+        if let Some(tap) = &self.tap {
+            out.push_str(&format!("# tap: {}\n", tap));
+        }
+        out.push_str(&format!("class {} < Formula\n", class_name));
+        out.push_str("  desc \"\"\n");
+        out.push_str("  homepage \"\"\n");
+        out.push_str(&format!("  url \"{}\"\n", self.url));
+        out.push_str("  sha256 \"\"\n");
+        if let Some(license) = &self.license {
+            out.push_str(&format!("  license \"{}\"\n", license));
+        }
+        out.push_str(&format!("  version \"{}\"\n", self.version));
+        out.push('\n');
+        out.push_str("  def install\n");
+        if let Some(mode) = self.mode {
+            out.push_str(install_body(mode));
+        }
+        out.push_str("  end\n");
+        out.push_str("end\n");
+        out
+    }
+}
+
+/// Infers a formula name from the last path segment of a source URL,
+/// stripping a known archive extension and a trailing `-<version>`.
+pub fn name_from_url(url: &str) -> String {
+    let last_segment = url.rsplit('/').next().unwrap_or(url);
+
+    let known_extensions = [".tar.gz", ".tar.bz2", ".tar.xz", ".tar.zst", ".tgz", ".zip", ".tar"];
+    let mut stem = last_segment;
+    for ext in known_extensions {
+        if let Some(stripped) = stem.strip_suffix(ext) {
+            stem = stripped;
+            break;
+        }
+    }
+
+    if let Some(idx) = stem.rfind('-') {
+        let (name_part, version_part) = (&stem[..idx], &stem[idx + 1..]);
+        if version_part.starts_with(|c: char| c.is_ascii_digit()) {
+            return name_part.to_string();
+        }
+    }
+
+    stem.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn name_from_url_strips_archive_extension_and_version() {
+        assert_eq!(name_from_url("https://ftp.gnu.org/gnu/wget/wget-1.21.3.tar.gz"), "wget");
+        assert_eq!(name_from_url("https://example.com/foo.zip"), "foo");
+    }
+
+    #[test]
+    fn render_round_trips_through_parse_formula() {
+        let _cleanup = unsafe { magnus::embed::init() };
+
+        let rendered = FormulaCreator::new(
+            "round-trip-sample",
+            "1.2.3",
+            "https://example.com/round-trip-sample-1.2.3.tar.gz",
+        )
+        .license("MIT")
+        .mode(Mode::Rust)
+        .render();
+
+        let path = std::env::temp_dir().join("round-trip-sample.rb");
+        std::fs::write(&path, &rendered).expect("failed to write generated formula");
+
+        let formula = crate::parse_formula(&path).expect("failed to parse generated formula");
+
+        assert_eq!(
+            formula.url.as_deref(),
+            Some("https://example.com/round-trip-sample-1.2.3.tar.gz")
+        );
+    }
+}
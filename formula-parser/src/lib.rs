@@ -4,6 +4,20 @@ use magnus::{Ruby, prelude::*};
 use std::fs;
 use std::path::Path;
 
+mod audit;
+mod cask;
+mod creator;
+mod loader;
+mod platform;
+pub use audit::{audit, AuditWarning, Severity};
+pub use cask::{parse_cask, Artifact, Cask};
+pub use creator::{name_from_url, FormulaCreator, Mode};
+pub use loader::{
+    canonical_name, FormulaLoader, Formulary, FromNameLoader, FromPathLoader, FromTapLoader,
+    FromURILoader,
+};
+pub use platform::{Arch, Os, TargetPlatform};
+
 #[derive(Debug, Default)]
 pub struct Formula {
     pub name: String,
@@ -11,13 +25,59 @@ pub struct Formula {
     pub homepage: Option<String>,
     pub url: Option<String>,
     pub sha256: Option<String>,
-    pub dependencies: Vec<String>,
+    pub dependencies: Vec<Dependency>,
+    pub bottles: Vec<Bottle>,
 }
 
-pub fn parse_formula(path: &Path) -> Result<Formula, magnus::Error> {
-    let file_stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
-    let class_name = file_stem
-        .split('-')
+/// A single platform's prebuilt-binary checksum from a formula's
+/// `bottle do ... end` block.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bottle {
+    pub tag: String,
+    pub sha256: String,
+    pub rebuild: Option<u32>,
+    pub root_url: Option<String>,
+}
+
+/// A single `depends_on`/`uses_from_macos` entry, with the tags that
+/// qualify when the dependency applies.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dependency {
+    pub name: String,
+    pub tags: Vec<DependencyTag>,
+}
+
+/// Qualifiers a formula can attach to a dependency, mirroring the
+/// `=> :build` / `=> :test` / `=> :optional` / `=> :recommended` hash
+/// syntax and the implicit system-provided tag from `uses_from_macos`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DependencyTag {
+    Build,
+    Test,
+    Optional,
+    Recommended,
+    UsesFromMacos { since: Option<String> },
+}
+
+fn dependency_tag_from_str(tag: &str) -> Option<DependencyTag> {
+    if let Some(rest) = tag.strip_prefix("uses_from_macos") {
+        let since = rest.strip_prefix(':').filter(|s| !s.is_empty()).map(String::from);
+        return Some(DependencyTag::UsesFromMacos { since });
+    }
+    match tag {
+        "build" => Some(DependencyTag::Build),
+        "test" => Some(DependencyTag::Test),
+        "optional" => Some(DependencyTag::Optional),
+        "recommended" => Some(DependencyTag::Recommended),
+        _ => None,
+    }
+}
+
+/// Derives a formula's Ruby class name from its file stem the way `brew`
+/// does: each `-`-separated part is capitalized and the parts are joined,
+/// e.g. `node-sass` -> `NodeSass`.
+pub(crate) fn class_name_from(stem: &str) -> String {
+    stem.split('-')
         .map(|part| {
             let mut c = part.chars();
             match c.next() {
@@ -25,7 +85,55 @@ pub fn parse_formula(path: &Path) -> Result<Formula, magnus::Error> {
                 Some(f) => f.to_uppercase().collect::<String>() + c.as_str(),
             }
         })
-        .collect::<String>();
+        .collect::<String>()
+}
+
+/// Stanzas whose body needs to be evaluated as a unit (rather than
+/// filtered line-by-line), because the methods they call —
+/// `sha256`/`url`/`depends_on`/etc. — only make sense executed together,
+/// in order, inside that block. Matched by keyword prefix rather than a
+/// literal `"<keyword> do"` string, since real formulae pass arguments
+/// before the `do`, e.g. `on_system :linux, macos: :sonoma_or_newer do`.
+const BLOCK_KEYWORDS: &[&str] = &["bottle", "on_macos", "on_linux", "on_arm", "on_intel", "on_system"];
+
+/// True if a trimmed line opens a block that will be closed by a bare
+/// `end` — a `do`/`do |...|` block, or Ruby's bodyless `if`/`unless`/
+/// `case`/`while`/`until`/`begin` forms. Deliberately excludes postfix
+/// modifiers (`foo if bar`), which don't open anything to balance.
+fn opens_block(trimmed: &str) -> bool {
+    if trimmed.ends_with(" do") || trimmed == "do" || (trimmed.contains(" do |") && trimmed.ends_with('|')) {
+        return true;
+    }
+    if trimmed == "begin" {
+        return true;
+    }
+    matches!(
+        trimmed.split_whitespace().next(),
+        Some("if") | Some("unless") | Some("case") | Some("while") | Some("until")
+    )
+}
+
+/// True if a trimmed line is the opening line of one of `BLOCK_KEYWORDS`'
+/// stanzas, e.g. `bottle do` or `on_system :linux, macos: :ventura do`.
+fn starts_tracked_stanza(trimmed: &str) -> bool {
+    BLOCK_KEYWORDS.iter().any(|keyword| {
+        trimmed
+            .strip_prefix(keyword)
+            .map(|rest| !rest.starts_with(|c: char| c.is_alphanumeric() || c == '_'))
+            .unwrap_or(false)
+    }) && opens_block(trimmed)
+}
+
+pub fn parse_formula(path: &Path) -> Result<Formula, magnus::Error> {
+    parse_formula_for_platform(path, TargetPlatform::host())
+}
+
+/// Like `parse_formula`, but evaluates `on_macos`/`on_linux`/`on_arm`/
+/// `on_intel`/`on_system` stanzas as the given platform would see them,
+/// so the resolved `url`/`sha256`/dependencies reflect that OS/arch.
+pub fn parse_formula_for_platform(path: &Path, platform: TargetPlatform) -> Result<Formula, magnus::Error> {
+    let file_stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+    let class_name = class_name_from(file_stem);
 
     if class_name.is_empty() {
         // Handle error for invalid file name
@@ -40,18 +148,36 @@ pub fn parse_formula(path: &Path) -> Result<Formula, magnus::Error> {
 
     // 3. Load the formula file's content and extract metadata lines
     let file_content = fs::read_to_string(path).expect("Could not read formula file.");
-    
-    // Extract only the metadata lines we care about (desc, homepage, url, sha256)
+
+    // Extract only the metadata lines we care about (desc, homepage, url, sha256,
+    // depends_on, uses_from_macos),
+    // but pull block stanzas (bottle/on_macos/on_linux/...) in whole, since their
+    // bodies only make sense evaluated together, in order, as Ruby blocks.
     let mut metadata_lines = Vec::new();
+    let mut block_depth = 0i32;
     for line in file_content.lines() {
         let trimmed = line.trim();
-        if trimmed.starts_with("desc ") || 
-           trimmed.starts_with("homepage ") || 
+        if block_depth > 0 {
+            metadata_lines.push(line);
+            if opens_block(trimmed) {
+                block_depth += 1;
+            } else if trimmed == "end" {
+                block_depth -= 1;
+            }
+            continue;
+        }
+        if starts_tracked_stanza(trimmed) {
+            metadata_lines.push(line);
+            block_depth = 1;
+        } else if trimmed.starts_with("desc ") ||
+           trimmed.starts_with("homepage ") ||
            trimmed.starts_with("url ") {
             metadata_lines.push(line);
         } else if trimmed.starts_with("sha256 \"") {
             // Only include the main sha256 line (not the bottle ones)
             metadata_lines.push(line);
+        } else if trimmed.starts_with("depends_on ") || trimmed.starts_with("uses_from_macos ") {
+            metadata_lines.push(line);
         }
     }
     let extracted_metadata = metadata_lines.join("\n");
@@ -61,7 +187,60 @@ pub fn parse_formula(path: &Path) -> Result<Formula, magnus::Error> {
     let inspector_code = format!(
         r#"
         require 'ostruct'
-        
+
+        TARGET_OS = :{}
+        TARGET_ARCH = :{}
+
+        # Minimal stand-in for Homebrew's Hardware::CPU, so formula bodies
+        # that branch on `Hardware::CPU.arm?`/`.intel?` (common inside
+        # on_macos/on_linux blocks) resolve against the chosen platform
+        # instead of raising NoMethodError.
+        class Hardware
+          class CPU
+            def self.arm?
+              TARGET_ARCH == :arm
+            end
+
+            def self.intel?
+              TARGET_ARCH == :intel
+            end
+          end
+        end
+
+        # Collects the per-platform checksums out of a `bottle do ... end`
+        # block so `self.bottle` can instance_exec the block against it.
+        class BottleRecorder
+          def initialize
+            @rebuild = nil
+            @root_url = nil
+            @entries = []
+          end
+
+          def sha256(*args)
+            opts = args.first.is_a?(Hash) ? args.first : {{}}
+            opts.each do |key, value|
+              next if key == :cellar || key == :rebuild
+              @entries << [key.to_s, value.to_s]
+            end
+          end
+
+          def rebuild(value = nil)
+            value ? (@rebuild = value) : @rebuild
+          end
+
+          def root_url(value = nil)
+            value ? (@root_url = value) : @root_url
+          end
+
+          def cellar(*args)
+            # Ignore for now
+          end
+
+          def entries
+            @entries.map {{ |tag, sha| [tag, sha, @rebuild, @root_url] }}
+          end
+        end
+
         # Define a base Formula class that tracks the values set by DSL methods
         class Formula
           @@formulas = {{}}
@@ -103,23 +282,79 @@ pub fn parse_formula(path: &Path) -> Result<Formula, magnus::Error> {
           end
           
           def self.depends_on(*args)
-            # Ignore for now
+            @@formulas[self.name] ||= {{}}
+            @@formulas[self.name][:dependencies] ||= []
+            args.each do |arg|
+              if arg.is_a?(Hash)
+                arg.each do |dep_name, tag|
+                  tags = Array(tag).map(&:to_s)
+                  @@formulas[self.name][:dependencies] << [dep_name.to_s, tags]
+                end
+              else
+                @@formulas[self.name][:dependencies] << [arg.to_s, []]
+              end
+            end
           end
-          
+
           def self.bottle(&block)
-            # Ignore for now
+            recorder = BottleRecorder.new
+            recorder.instance_exec(&block) if block
+            @@formulas[self.name] ||= {{}}
+            @@formulas[self.name][:bottles] = recorder.entries
           end
-          
+
+          def self.bottles
+            @@formulas[self.name]&.dig(:bottles) || []
+          end
+
           def self.license(value)
             # Ignore for now
           end
-          
+
           def self.mirror(value)
             # Ignore for now
           end
-          
+
           def self.uses_from_macos(*args)
-            # Ignore for now
+            @@formulas[self.name] ||= {{}}
+            @@formulas[self.name][:dependencies] ||= []
+            dep_name = args[0]
+            opts = args[1].is_a?(Hash) ? args[1] : {{}}
+            since = opts[:since]
+            tag = since ? "uses_from_macos:#{{since}}" : "uses_from_macos"
+            @@formulas[self.name][:dependencies] << [dep_name.to_s, [tag]]
+          end
+
+          # OnSystem DSL: only evaluate the block when it matches the
+          # target platform the inspector was configured for, so a single
+          # formula's per-platform url/sha256/deps resolve correctly.
+          def self.on_macos(&block)
+            instance_exec(&block) if block && TARGET_OS == :macos
+          end
+
+          def self.on_linux(&block)
+            instance_exec(&block) if block && TARGET_OS == :linux
+          end
+
+          def self.on_arm(&block)
+            instance_exec(&block) if block && TARGET_ARCH == :arm
+          end
+
+          def self.on_intel(&block)
+            instance_exec(&block) if block && TARGET_ARCH == :intel
+          end
+
+          def self.on_system(system, **_opts, &block)
+            matches = case system
+                      when :linux then TARGET_OS == :linux
+                      when :macos then TARGET_OS == :macos
+                      else true
+                      end
+            instance_exec(&block) if block && matches
+          end
+
+          def self.dependencies
+            @@formulas[self.name]&.dig(:dependencies) || []
           end
           
           def install
@@ -189,7 +424,7 @@ pub fn parse_formula(path: &Path) -> Result<Formula, magnus::Error> {
           {}
         end
     "#,
-        class_name, extracted_metadata
+        platform.os_symbol(), platform.arch_symbol(), class_name, extracted_metadata
     );
 
     ruby.eval::<magnus::Value>(&inspector_code)?;
@@ -203,8 +438,27 @@ pub fn parse_formula(path: &Path) -> Result<Formula, magnus::Error> {
     let url: Option<String> = formula_class.funcall("url", ()).ok();
     let sha256: Option<String> = formula_class.funcall("sha256", ()).ok();
 
-    // More complex fields like dependencies might require more detailed parsing,
-    // but for now, we'll keep it simple.
+    let raw_dependencies: Vec<(String, Vec<String>)> =
+        formula_class.funcall("dependencies", ()).unwrap_or_default();
+    let dependencies = raw_dependencies
+        .into_iter()
+        .map(|(name, tags)| Dependency {
+            name,
+            tags: tags.iter().filter_map(|t| dependency_tag_from_str(t)).collect(),
+        })
+        .collect();
+
+    let raw_bottles: Vec<(String, String, Option<u32>, Option<String>)> =
+        formula_class.funcall("bottles", ()).unwrap_or_default();
+    let bottles = raw_bottles
+        .into_iter()
+        .map(|(tag, sha256, rebuild, root_url)| Bottle {
+            tag,
+            sha256,
+            rebuild,
+            root_url,
+        })
+        .collect();
 
     Ok(Formula {
         name: file_stem.to_string(),
@@ -212,7 +466,8 @@ pub fn parse_formula(path: &Path) -> Result<Formula, magnus::Error> {
         homepage,
         url,
         sha256,
-        dependencies: Vec::new(), // Placeholder for now
+        dependencies,
+        bottles,
     })
 }
 
@@ -238,4 +493,76 @@ mod tests {
         assert!(formula.url.is_some());
         assert!(formula.sha256.is_some());
     }
+
+    #[test]
+    fn it_parses_tagged_dependencies() {
+        let _cleanup = unsafe { magnus::embed::init() };
+
+        let path_to_formula = Path::new("tests/fixtures/depends-on-tags.rb");
+        let formula = parse_formula(path_to_formula).expect("Failed to parse formula");
+
+        assert_eq!(
+            formula.dependencies,
+            vec![
+                Dependency { name: "runtime-only-dep".to_string(), tags: vec![] },
+                Dependency {
+                    name: "build-and-test-dep".to_string(),
+                    tags: vec![DependencyTag::Build, DependencyTag::Test],
+                },
+                Dependency {
+                    name: "optional-dep".to_string(),
+                    tags: vec![DependencyTag::Optional],
+                },
+                Dependency {
+                    name: "recommended-dep".to_string(),
+                    tags: vec![DependencyTag::Recommended],
+                },
+                Dependency {
+                    name: "zlib".to_string(),
+                    tags: vec![DependencyTag::UsesFromMacos { since: None }],
+                },
+                Dependency {
+                    name: "libxml2".to_string(),
+                    tags: vec![DependencyTag::UsesFromMacos { since: Some("catalina".to_string()) }],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn it_parses_modern_bottle_checksums() {
+        let _cleanup = unsafe { magnus::embed::init() };
+
+        let path_to_formula = Path::new("tests/fixtures/bottle-modern.rb");
+        let formula = parse_formula(path_to_formula).expect("Failed to parse formula");
+
+        assert_eq!(formula.bottles.len(), 3);
+        assert!(formula.bottles.iter().all(|b| b.rebuild == Some(1)));
+        assert!(formula
+            .bottles
+            .iter()
+            .all(|b| b.root_url.as_deref() == Some("https://example.com/bottles")));
+        assert!(formula.bottles.iter().any(|b| b.tag == "arm64_sonoma" && b.sha256 == "e".repeat(64)));
+    }
+
+    #[test]
+    fn it_resolves_on_system_blocks_per_platform() {
+        let _cleanup = unsafe { magnus::embed::init() };
+
+        let path_to_formula = Path::new("tests/fixtures/on-system-sample.rb");
+
+        let macos_arm = TargetPlatform { os: Os::Macos, arch: Arch::Arm };
+        let formula = parse_formula_for_platform(path_to_formula, macos_arm)
+            .expect("Failed to parse formula for macOS/arm");
+        assert_eq!(formula.url.as_deref(), Some("https://example.com/on-system-sample-macos.tar.gz"));
+        assert!(formula.dependencies.iter().any(|d| d.name == "arm-only-dep"));
+        assert!(!formula.dependencies.iter().any(|d| d.name == "linux-only-dep"));
+
+        let linux_intel = TargetPlatform { os: Os::Linux, arch: Arch::Intel };
+        let formula = parse_formula_for_platform(path_to_formula, linux_intel)
+            .expect("Failed to parse formula for Linux/intel");
+        assert_eq!(formula.url.as_deref(), Some("https://example.com/on-system-sample-linux.tar.gz"));
+        assert!(formula.dependencies.iter().any(|d| d.name == "linux-only-dep"));
+        assert!(!formula.dependencies.iter().any(|d| d.name == "arm-only-dep"));
+    }
 }
\ No newline at end of file
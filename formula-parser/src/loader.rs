@@ -0,0 +1,221 @@
+// formula-parser/src/loader.rs
+//
+// Mirrors Homebrew's Formulary: given some reference to a formula (a path,
+// a bare name, a `user/repo/name` tap reference, or a raw URL), resolve it
+// to a parsed `Formula` without the caller having to know which shape the
+// reference takes.
+
+use crate::{parse_formula, Formula};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Something that knows how to turn itself into a parsed `Formula`.
+pub trait FormulaLoader {
+    fn load(&self) -> Result<Formula, magnus::Error>;
+}
+
+/// Loads a formula directly from a `.rb` file on disk. This is the
+/// behavior `parse_formula` always had; the other loaders just produce a
+/// path for this one to use.
+pub struct FromPathLoader {
+    pub path: PathBuf,
+}
+
+impl FormulaLoader for FromPathLoader {
+    fn load(&self) -> Result<Formula, magnus::Error> {
+        parse_formula(&self.path)
+    }
+}
+
+/// Loads a formula by name out of a single configured tap's `Formula/`
+/// directory, e.g. `homebrew/core/Formula/<name>.rb`.
+pub struct FromNameLoader {
+    pub name: String,
+    pub tap_dir: PathBuf,
+}
+
+impl FormulaLoader for FromNameLoader {
+    fn load(&self) -> Result<Formula, magnus::Error> {
+        let path = self.tap_dir.join("Formula").join(format!("{}.rb", self.name));
+        FromPathLoader { path }.load()
+    }
+}
+
+/// Loads a formula given a fully qualified `user/repo/name` tap reference,
+/// resolving it against a directory of checked-out taps.
+pub struct FromTapLoader {
+    pub user: String,
+    pub repo: String,
+    pub name: String,
+    pub taps_dir: PathBuf,
+}
+
+impl FormulaLoader for FromTapLoader {
+    fn load(&self) -> Result<Formula, magnus::Error> {
+        let tap_dir = self
+            .taps_dir
+            .join(&self.user)
+            .join(format!("homebrew-{}", self.repo));
+        FromNameLoader {
+            name: self.name.clone(),
+            tap_dir,
+        }
+        .load()
+    }
+}
+
+/// Downloads a raw `.rb` formula from a URL to a temp file, then parses it
+/// from disk like any other formula.
+pub struct FromURILoader {
+    pub uri: String,
+}
+
+impl FormulaLoader for FromURILoader {
+    fn load(&self) -> Result<Formula, magnus::Error> {
+        let output = Command::new("curl")
+            .args(["-fsSL", &self.uri])
+            .output()
+            .map_err(|e| magnus::Error::new(magnus::exception::runtime_error(), e.to_string()))?;
+
+        if !output.status.success() {
+            return Err(magnus::Error::new(
+                magnus::exception::runtime_error(),
+                format!("Failed to download formula from {}", self.uri),
+            ));
+        }
+
+        let file_name = canonical_name(&self.uri);
+        let temp_path = std::env::temp_dir().join(format!("{}.rb", file_name));
+        std::fs::write(&temp_path, &output.stdout)
+            .map_err(|e| magnus::Error::new(magnus::exception::runtime_error(), e.to_string()))?;
+
+        FromPathLoader { path: temp_path }.load()
+    }
+}
+
+/// Strips a tap prefix (`user/repo/`) and a `.rb`/`.json` suffix off a
+/// formula reference, leaving just the formula's stem — the same name
+/// `parse_formula` would derive from a bare file path.
+pub fn canonical_name(reference: &str) -> String {
+    let last_segment = reference.rsplit('/').next().unwrap_or(reference);
+    last_segment
+        .strip_suffix(".rb")
+        .or_else(|| last_segment.strip_suffix(".json"))
+        .unwrap_or(last_segment)
+        .to_string()
+}
+
+fn default_taps_dir() -> PathBuf {
+    std::env::var("HOMEBREW_TAPS_ROOT")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from("/usr/local/Homebrew/Library/Taps"))
+}
+
+fn default_core_tap_dir() -> PathBuf {
+    default_taps_dir().join("homebrew").join("homebrew-core")
+}
+
+/// Picks the right `FormulaLoader` for a reference and loads it, the way
+/// `Formulary.factory` does in Homebrew: an absolute path loads straight
+/// from disk, a `http(s)://` URL is downloaded first, a `user/repo/name`
+/// reference resolves against a tap, and anything else is treated as a
+/// bare name in `homebrew/core`.
+pub struct Formulary;
+
+impl Formulary {
+    pub fn load(reference: &str) -> Result<Formula, magnus::Error> {
+        if reference.starts_with("http://") || reference.starts_with("https://") {
+            return FromURILoader {
+                uri: reference.to_string(),
+            }
+            .load();
+        }
+
+        if reference.starts_with('/') || Path::new(reference).is_absolute() {
+            return FromPathLoader {
+                path: PathBuf::from(reference),
+            }
+            .load();
+        }
+
+        let parts: Vec<&str> = reference.split('/').collect();
+        if parts.len() == 3 {
+            return FromTapLoader {
+                user: parts[0].to_string(),
+                repo: parts[1].to_string(),
+                name: canonical_name(parts[2]),
+                taps_dir: default_taps_dir(),
+            }
+            .load();
+        }
+
+        FromNameLoader {
+            name: canonical_name(reference),
+            tap_dir: default_core_tap_dir(),
+        }
+        .load()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn canonical_name_strips_tap_prefix_and_extension() {
+        assert_eq!(canonical_name("wget"), "wget");
+        assert_eq!(canonical_name("wget.rb"), "wget");
+        assert_eq!(canonical_name("homebrew/core/wget.rb"), "wget");
+        assert_eq!(canonical_name("user/repo/wget.json"), "wget");
+    }
+
+    #[test]
+    fn from_path_loader_delegates_to_parse_formula() {
+        let _cleanup = unsafe { magnus::embed::init() };
+
+        let loader = FromPathLoader {
+            path: PathBuf::from("tests/fixtures/depends-on-tags.rb"),
+        };
+        let formula = loader.load().expect("Failed to load formula from path");
+        assert_eq!(formula.name, "depends-on-tags");
+    }
+
+    #[test]
+    fn formulary_load_strips_extension_from_tap_reference_name() {
+        let _cleanup = unsafe { magnus::embed::init() };
+
+        let taps_root = std::env::temp_dir().join(format!("formulary-test-taps-{}", std::process::id()));
+        let formula_dir = taps_root.join("testuser").join("homebrew-testrepo").join("Formula");
+        std::fs::create_dir_all(&formula_dir).expect("failed to create fake taps dir");
+        std::fs::write(
+            formula_dir.join("mytool.rb"),
+            "class Mytool < Formula\n  desc \"Test tool\"\n  homepage \"https://example.com\"\n  url \"https://example.com/mytool-1.0.tar.gz\"\n  sha256 \"\"\nend\n",
+        )
+        .expect("failed to write fake formula");
+
+        std::env::set_var("HOMEBREW_TAPS_ROOT", &taps_root);
+        let result = Formulary::load("testuser/testrepo/mytool.rb");
+        std::env::remove_var("HOMEBREW_TAPS_ROOT");
+        std::fs::remove_dir_all(&taps_root).ok();
+
+        // Before the fix this looked up `Formula/mytool.rb.rb`, which never
+        // exists, because the tap reference's name segment wasn't run
+        // through `canonical_name` like the bare-name fallback branch is.
+        let formula = result.expect("Failed to load formula via extension-bearing tap reference");
+        assert_eq!(formula.name, "mytool");
+        assert_eq!(formula.url.as_deref(), Some("https://example.com/mytool-1.0.tar.gz"));
+    }
+
+    #[test]
+    fn formulary_load_dispatches_absolute_paths_to_from_path_loader() {
+        let _cleanup = unsafe { magnus::embed::init() };
+
+        let absolute_path = std::fs::canonicalize("tests/fixtures/depends-on-tags.rb")
+            .expect("fixture should exist")
+            .to_str()
+            .expect("path should be valid UTF-8")
+            .to_string();
+        let formula = Formulary::load(&absolute_path).expect("Failed to load formula by path");
+        assert_eq!(formula.name, "depends-on-tags");
+    }
+}
@@ -0,0 +1,51 @@
+// formula-parser/src/platform.rs
+//
+// The OS/arch a formula's `on_macos`/`on_linux`/`on_arm`/`on_intel` stanzas
+// should be evaluated against, mirroring Homebrew's OnSystem DSL.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Os {
+    Macos,
+    Linux,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Arch {
+    Arm,
+    Intel,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TargetPlatform {
+    pub os: Os,
+    pub arch: Arch,
+}
+
+impl TargetPlatform {
+    /// The OS/arch this binary itself was compiled for.
+    pub fn host() -> Self {
+        let os = if cfg!(target_os = "macos") { Os::Macos } else { Os::Linux };
+        let arch = if cfg!(target_arch = "aarch64") { Arch::Arm } else { Arch::Intel };
+        Self { os, arch }
+    }
+
+    pub(crate) fn os_symbol(&self) -> &'static str {
+        match self.os {
+            Os::Macos => "macos",
+            Os::Linux => "linux",
+        }
+    }
+
+    pub(crate) fn arch_symbol(&self) -> &'static str {
+        match self.arch {
+            Arch::Arm => "arm",
+            Arch::Intel => "intel",
+        }
+    }
+}
+
+impl Default for TargetPlatform {
+    fn default() -> Self {
+        Self::host()
+    }
+}